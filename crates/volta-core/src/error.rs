@@ -0,0 +1,113 @@
+//! The error taxonomy for `volta-core`. Each [`ErrorDetails`] variant carries the
+//! context needed to render a user-facing message and to pick an exit code.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use volta_fail::{ExitCode, VoltaFail};
+
+#[derive(Debug)]
+pub enum ErrorDetails {
+    /// Thrown when the cached Node index could not be read from disk.
+    ReadNodeIndexCacheError { file: PathBuf },
+
+    /// Thrown when the cached Node index expiry could not be read from disk.
+    ReadNodeIndexExpiryError { file: PathBuf },
+
+    /// Thrown when the cached Node index could not be written to disk.
+    WriteNodeIndexCacheError { file: PathBuf },
+
+    /// Thrown when the cached Node index expiry could not be written to disk.
+    WriteNodeIndexExpiryError { file: PathBuf },
+
+    /// Thrown when a cached Node index file could not be removed.
+    DeleteNodeIndexCacheError { file: PathBuf },
+
+    /// Thrown when a request to a tool registry could not be completed.
+    RegistryFetchError { tool: String, from_url: String },
+
+    /// Thrown when the Node index freshly downloaded from the registry could not
+    /// be parsed.
+    ParseNodeIndexError { from_url: String },
+
+    /// Thrown when the cached Node index could not be parsed.
+    ParseNodeIndexCacheError,
+
+    /// Thrown when the cached Node index expiry could not be parsed.
+    ParseNodeIndexExpiryError,
+
+    /// Thrown when a version or requirement string could not be parsed.
+    ParseVersionError { value: String },
+
+    /// Thrown when no Node version matching the request could be found.
+    NodeVersionNotFound { matching: String },
+
+    /// Thrown when a temporary file could not be created.
+    CreateTempFileError { in_dir: PathBuf },
+}
+
+impl fmt::Display for ErrorDetails {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorDetails::ReadNodeIndexCacheError { file } => {
+                write!(f, "Could not read Node index cache from {}", file.display())
+            }
+            ErrorDetails::ReadNodeIndexExpiryError { file } => write!(
+                f,
+                "Could not read Node index cache expiry from {}",
+                file.display()
+            ),
+            ErrorDetails::WriteNodeIndexCacheError { file } => {
+                write!(f, "Could not write Node index cache to {}", file.display())
+            }
+            ErrorDetails::WriteNodeIndexExpiryError { file } => write!(
+                f,
+                "Could not write Node index cache expiry to {}",
+                file.display()
+            ),
+            ErrorDetails::DeleteNodeIndexCacheError { file } => {
+                write!(f, "Could not remove Node index cache file {}", file.display())
+            }
+            ErrorDetails::RegistryFetchError { tool, from_url } => {
+                write!(f, "Could not fetch {} version registry from {}", tool, from_url)
+            }
+            ErrorDetails::ParseNodeIndexError { from_url } => {
+                write!(f, "Could not parse Node version index from {}", from_url)
+            }
+            ErrorDetails::ParseNodeIndexCacheError => {
+                write!(f, "Could not parse Node index cache")
+            }
+            ErrorDetails::ParseNodeIndexExpiryError => {
+                write!(f, "Could not parse Node index cache expiry")
+            }
+            ErrorDetails::ParseVersionError { value } => {
+                write!(f, "Could not parse version '{}'", value)
+            }
+            ErrorDetails::NodeVersionNotFound { matching } => {
+                write!(f, "Could not find Node version matching '{}'", matching)
+            }
+            ErrorDetails::CreateTempFileError { in_dir } => {
+                write!(f, "Could not create temporary file in {}", in_dir.display())
+            }
+        }
+    }
+}
+
+impl VoltaFail for ErrorDetails {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            ErrorDetails::ReadNodeIndexCacheError { .. }
+            | ErrorDetails::ReadNodeIndexExpiryError { .. }
+            | ErrorDetails::WriteNodeIndexCacheError { .. }
+            | ErrorDetails::WriteNodeIndexExpiryError { .. }
+            | ErrorDetails::DeleteNodeIndexCacheError { .. }
+            | ErrorDetails::CreateTempFileError { .. } => ExitCode::FileSystemError,
+            ErrorDetails::RegistryFetchError { .. } => ExitCode::NetworkError,
+            ErrorDetails::ParseNodeIndexError { .. }
+            | ErrorDetails::ParseNodeIndexCacheError
+            | ErrorDetails::ParseNodeIndexExpiryError
+            | ErrorDetails::ParseVersionError { .. } => ExitCode::UnknownError,
+            ErrorDetails::NodeVersionNotFound { .. } => ExitCode::NoVersionMatch,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Resolves the on-disk locations Volta uses, rooted at the Volta home directory.
+
+use std::env;
+use std::path::PathBuf;
+
+use crate::error::ErrorDetails;
+use volta_fail::{Fallible, ResultExt};
+
+/// The Volta home directory, honoring `VOLTA_HOME` and otherwise defaulting to
+/// `~/.volta`.
+fn volta_home() -> Fallible<PathBuf> {
+    if let Some(home) = env::var_os("VOLTA_HOME") {
+        return Ok(PathBuf::from(home));
+    }
+
+    dirs::home_dir()
+        .map(|home| home.join(".volta"))
+        .ok_or_else(|| ErrorDetails::CreateTempFileError {
+            in_dir: PathBuf::from("~"),
+        })
+        .map_err(Into::into)
+}
+
+/// The directory holding the cached Node version index and its metadata.
+fn node_cache_dir() -> Fallible<PathBuf> {
+    Ok(volta_home()?.join("tmp").join("node-cache"))
+}
+
+/// The cached copy of the public Node version index.
+pub fn node_index_file() -> Fallible<PathBuf> {
+    Ok(node_cache_dir()?.join("index.json"))
+}
+
+/// The sidecar recording when the cached Node index expires.
+pub fn node_index_expiry_file() -> Fallible<PathBuf> {
+    Ok(node_cache_dir()?.join("index.json.expires"))
+}
+
+/// The sidecar recording the `ETag`/`Last-Modified` validators used to
+/// conditionally revalidate the cached Node index.
+pub fn node_index_etag_file() -> Fallible<PathBuf> {
+    Ok(node_cache_dir()?.join("index.json.etag"))
+}
+
+/// The directory temporary files are staged in before being persisted.
+pub fn tmp_dir() -> Fallible<PathBuf> {
+    let tmp = volta_home()?.join("tmp");
+    std::fs::create_dir_all(&tmp).with_context(|_| ErrorDetails::CreateTempFileError {
+        in_dir: tmp.clone(),
+    })?;
+    Ok(tmp)
+}
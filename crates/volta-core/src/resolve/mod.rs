@@ -0,0 +1,18 @@
+//! Resolves a [`VersionSpec`](crate::version::VersionSpec) to a concrete tool
+//! version by consulting the relevant registry.
+
+pub mod node;
+mod serial;
+
+use crate::error::ErrorDetails;
+
+/// Builds the error used when a registry request fails, capturing the tool name
+/// and source URL for the user-facing message.
+pub fn registry_fetch_error(
+    tool: impl AsRef<str>,
+    from_url: impl AsRef<str>,
+) -> impl FnOnce(&reqwest::Error) -> ErrorDetails {
+    let tool = tool.as_ref().to_string();
+    let from_url = from_url.as_ref().to_string();
+    move |_| ErrorDetails::RegistryFetchError { tool, from_url }
+}
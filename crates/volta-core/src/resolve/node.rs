@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 use std::fs::File;
 use std::io::Write;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, SystemTime};
 
 use super::{registry_fetch_error, serial};
@@ -16,10 +18,13 @@ use cfg_if::cfg_if;
 use headers_011::Headers011;
 use log::debug;
 use reqwest;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
 use reqwest::hyper_011::header::{CacheControl, CacheDirective, Expires, HttpDate};
 use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tempfile::NamedTempFile;
-use volta_fail::{throw, Fallible, ResultExt};
+use volta_fail::{throw, Fallible, ResultExt, VoltaError};
 
 // ISSUE (#86): Move public repository URLs to config file
 cfg_if! {
@@ -39,25 +44,31 @@ pub fn resolve(matching: VersionSpec, hooks: Option<&ToolHooks<NodeDistro>>) ->
     match matching {
         VersionSpec::Latest => resolve_latest(hooks),
         VersionSpec::Lts => resolve_lts(hooks),
+        VersionSpec::LtsLine(codename) => resolve_lts_line(&codename, hooks),
         VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks),
         VersionSpec::Exact(version) => Ok(version),
     }
 }
 
-fn resolve_latest(hooks: Option<&ToolHooks<NodeDistro>>) -> Fallible<Version> {
-    // NOTE: This assumes the registry always produces a list in sorted order
-    //       from newest to oldest. This should be specified as a requirement
-    //       when we document the plugin API.
-    let url = match hooks {
+/// Determines the URL of the Node version index, honoring a `node.latest` hook if present.
+fn node_index_url(hooks: Option<&ToolHooks<NodeDistro>>) -> Fallible<String> {
+    match hooks {
         Some(&ToolHooks {
             latest: Some(ref hook),
             ..
         }) => {
             debug!("Using node.latest hook to determine node index URL");
-            hook.resolve("index.json")?
+            hook.resolve("index.json")
         }
-        _ => public_node_version_index(),
-    };
+        _ => Ok(public_node_version_index()),
+    }
+}
+
+fn resolve_latest(hooks: Option<&ToolHooks<NodeDistro>>) -> Fallible<Version> {
+    // NOTE: This assumes the registry always produces a list in sorted order
+    //       from newest to oldest. This should be specified as a requirement
+    //       when we document the plugin API.
+    let url = node_index_url(hooks)?;
     let version_opt = match_node_version(&url, |_| true)?;
 
     if let Some(version) = version_opt {
@@ -70,15 +81,64 @@ fn resolve_latest(hooks: Option<&ToolHooks<NodeDistro>>) -> Fallible<Version> {
     }
 }
 
-fn resolve_lts(_hooks: Option<&ToolHooks<NodeDistro>>) -> Fallible<Version> {
-    VersionSpec::parse_version("1.0.0")
+fn resolve_lts(hooks: Option<&ToolHooks<NodeDistro>>) -> Fallible<Version> {
+    let url = node_index_url(hooks)?;
+    // The index is newest-to-oldest, so the first entry carrying an LTS codename
+    // is the newest LTS release.
+    let version_opt = match_node_version(&url, |entry| entry.lts.is_some())?;
+
+    if let Some(version) = version_opt {
+        debug!("Found newest LTS node version ({}) from {}", version, url);
+        Ok(version)
+    } else {
+        throw!(ErrorDetails::NodeVersionNotFound {
+            matching: "lts".to_string()
+        })
+    }
+}
+
+fn resolve_lts_line(codename: &str, hooks: Option<&ToolHooks<NodeDistro>>) -> Fallible<Version> {
+    let url = node_index_url(hooks)?;
+    let version_opt = match_node_version(&url, |entry| {
+        entry
+            .lts
+            .as_ref()
+            .map_or(false, |line| line.eq_ignore_ascii_case(codename))
+    })?;
+
+    if let Some(version) = version_opt {
+        debug!(
+            "Found newest node version ({}) on LTS line '{}' from {}",
+            version, codename, url
+        );
+        Ok(version)
+    } else {
+        throw!(ErrorDetails::NodeVersionNotFound {
+            matching: format!("lts/{}", codename)
+        })
+    }
 }
 
 fn resolve_semver(
-    _requirement: VersionReq,
-    _hooks: Option<&ToolHooks<NodeDistro>>,
+    requirement: VersionReq,
+    hooks: Option<&ToolHooks<NodeDistro>>,
 ) -> Fallible<Version> {
-    VersionSpec::parse_version("1.0.0")
+    let url = node_index_url(hooks)?;
+    // The index is newest-to-oldest, so the first matching entry is the newest
+    // version satisfying the requirement.
+    let version_opt = match_node_version(&url, |entry| requirement.matches(&entry.version))?;
+
+    if let Some(version) = version_opt {
+        debug!(
+            "Found node version ({}) matching '{}' from {}",
+            version, requirement, url
+        );
+        Ok(version)
+    } else {
+        throw!(ErrorDetails::NodeVersionNotFound {
+            matching: requirement.to_string()
+        })
+    }
 }
 
 fn match_node_version(
@@ -102,7 +162,9 @@ pub struct NodeEntry {
     pub version: Version,
     pub npm: Version,
     pub files: NodeDistroFiles,
-    pub lts: bool,
+    /// The release-line codename (e.g. `"Gallium"`) if this is an LTS release,
+    /// or `None` for a non-LTS (`"lts": false` in the index) release.
+    pub lts: Option<String>,
 }
 
 /// The set of available files on the public Node server for a given Node version.
@@ -111,8 +173,157 @@ pub struct NodeDistroFiles {
     pub files: HashSet<String>,
 }
 
-/// Reads a public index from the Node cache, if it exists and hasn't expired.
-fn read_cached_opt() -> Fallible<Option<serial::RawNodeIndex>> {
+/// When set, the next index resolution bypasses `read_cached_opt` and goes to the
+/// registry whatever the expiry says. Set via [`force_node_index_refresh`].
+static FORCE_INDEX_REFRESH: AtomicBool = AtomicBool::new(false);
+
+/// Forces the next Node index resolution to skip the expiry check and request the
+/// index from the registry. The request still carries any recorded validators, so
+/// a 304 Not Modified reply reuses the cached copy, as does a failure to reach
+/// the registry.
+pub fn force_node_index_refresh() {
+    FORCE_INDEX_REFRESH.store(true, Ordering::SeqCst);
+}
+
+/// Removes the cached Node index together with all of its sidecar metadata (the
+/// expiry and revalidation files), so the next resolution starts from scratch.
+pub fn clear_node_index_cache() -> Fallible<()> {
+    remove_cache_file(path::node_index_file()?)?;
+    remove_cache_file(path::node_index_expiry_file()?)?;
+    remove_cache_file(path::node_index_etag_file()?)?;
+    Ok(())
+}
+
+/// Removes a single cache file, treating an already-absent file as success.
+fn remove_cache_file(file: PathBuf) -> Fallible<()> {
+    if file.exists() {
+        std::fs::remove_file(&file)
+            .with_context(|_| ErrorDetails::DeleteNodeIndexCacheError { file })?;
+    }
+    Ok(())
+}
+
+/// The HTTP validators recorded alongside the cached index, used to revalidate
+/// it conditionally once the local expiry has passed.
+#[derive(Serialize, Deserialize)]
+struct CacheValidators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    /// Extracts the `ETag`/`Last-Modified` validators from a fresh response.
+    fn from_response(response: &reqwest::Response) -> CacheValidators {
+        let header_value = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        CacheValidators {
+            etag: header_value(ETAG),
+            last_modified: header_value(LAST_MODIFIED),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Marker prefixing the header line embedded at the top of the cached index.
+const CACHE_HEADER_PREFIX: &str = "//volta-cache:";
+
+/// The self-describing header embedded in the cached index file, identifying the
+/// source it was fetched from and fingerprinting the body that follows it.
+#[derive(Serialize, Deserialize)]
+struct CacheHeader {
+    url: String,
+    fingerprint: String,
+}
+
+/// Computes the content fingerprint (SHA-256) of a cached index body.
+fn fingerprint(body: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.input(body.as_bytes());
+    format!("{:x}", hasher.result())
+}
+
+/// Reads the cached index body, validating the embedded source URL and content
+/// fingerprint. Returns `None` — the cache is treated as absent — when the file
+/// is missing, lacks its header, was fetched from a different `url`, or no longer
+/// matches its fingerprint (e.g. a partial or corrupt write).
+fn read_cached_index_body(url: &str) -> Fallible<Option<String>> {
+    let index_file = path::node_index_file()?;
+    let raw = read_file_opt(&index_file)
+        .with_context(|_| ErrorDetails::ReadNodeIndexCacheError { file: index_file })?;
+
+    let raw = match raw {
+        Some(raw) => raw,
+        None => return Ok(None),
+    };
+
+    // The header occupies the first line; the index body is everything after it.
+    let (header_line, body) = match raw.find('\n') {
+        Some(split) => (&raw[..split], &raw[split + 1..]),
+        None => {
+            debug!("Cached Node index is missing its header; ignoring");
+            return Ok(None);
+        }
+    };
+
+    let header_json = match header_line.strip_prefix(CACHE_HEADER_PREFIX) {
+        Some(json) => json,
+        None => {
+            debug!("Cached Node index is missing its header; ignoring");
+            return Ok(None);
+        }
+    };
+
+    let header: CacheHeader = match serde_json::de::from_str(header_json) {
+        Ok(header) => header,
+        Err(_) => {
+            debug!("Cached Node index has an unreadable header; ignoring");
+            return Ok(None);
+        }
+    };
+
+    if header.url != url {
+        debug!(
+            "Cached Node index was fetched from {}, not {}; ignoring",
+            header.url, url
+        );
+        return Ok(None);
+    }
+
+    if header.fingerprint != fingerprint(body) {
+        debug!("Cached Node index failed its fingerprint check; ignoring");
+        return Ok(None);
+    }
+
+    Ok(Some(body.to_string()))
+}
+
+/// Reads the cached revalidation metadata (ETag / Last-Modified), if present.
+fn read_cached_validators() -> Fallible<Option<CacheValidators>> {
+    let etag_file = path::node_index_etag_file()?;
+    let contents = read_file_opt(&etag_file)
+        .with_context(|_| ErrorDetails::ReadNodeIndexCacheError { file: etag_file })?;
+
+    match contents {
+        Some(string) => serde_json::de::from_str(&string)
+            .map(Some)
+            .with_context(|_| ErrorDetails::ParseNodeIndexCacheError),
+        None => Ok(None),
+    }
+}
+
+/// Reads a public index from the Node cache, if it exists, hasn't expired, and
+/// still matches the requested `url` and its embedded fingerprint.
+fn read_cached_opt(url: &str) -> Fallible<Option<serial::RawNodeIndex>> {
     let expiry_file = path::node_index_expiry_file()?;
     let expiry = read_file_opt(&expiry_file)
         .with_context(|_| ErrorDetails::ReadNodeIndexExpiryError { file: expiry_file })?;
@@ -123,11 +334,7 @@ fn read_cached_opt() -> Fallible<Option<serial::RawNodeIndex>> {
         let current_date = HttpDate::from(SystemTime::now());
 
         if current_date < expiry_date {
-            let index_file = path::node_index_file()?;
-            let cached = read_file_opt(&index_file)
-                .with_context(|_| ErrorDetails::ReadNodeIndexCacheError { file: index_file })?;
-
-            if let Some(string) = cached {
+            if let Some(string) = read_cached_index_body(url)? {
                 return serde_json::de::from_str(&string)
                     .with_context(|_| ErrorDetails::ParseNodeIndexCacheError);
             }
@@ -151,84 +358,176 @@ fn max_age(response: &reqwest::Response) -> u32 {
     4 * 60 * 60
 }
 
-fn resolve_node_versions(url: &str) -> Fallible<serial::RawNodeIndex> {
-    match read_cached_opt()? {
-        Some(serial) => Ok(serial),
-        None => {
-            let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
+/// Atomically persists `contents` to `destination`, staging through a temp file
+/// in the Volta tmp dir so a partial write never clobbers an existing cache file.
+fn persist_cache_file(
+    contents: &[u8],
+    destination: PathBuf,
+    make_error: impl Fn(PathBuf) -> ErrorDetails,
+) -> Fallible<()> {
+    let tmp_root = path::tmp_dir()?;
+    let tmp = NamedTempFile::new_in(&tmp_root).with_context(|_| {
+        ErrorDetails::CreateTempFileError {
+            in_dir: tmp_root.clone(),
+        }
+    })?;
 
-            let mut response: reqwest::Response =
-                reqwest::get(url).with_context(registry_fetch_error("Node", url))?;
-            let response_text = response
-                .text()
-                .with_context(registry_fetch_error("Node", url))?;
-            let index: serial::RawNodeIndex = serde_json::de::from_str(&response_text)
-                .with_context(|_| ErrorDetails::ParseNodeIndexError {
-                    from_url: url.to_string(),
-                })?;
-
-            let tmp_root = path::tmp_dir()?;
-            // Helper to lazily determine temp dir string, without moving the file into the closures below
-            let get_tmp_root = || tmp_root.to_owned();
-
-            let cached = NamedTempFile::new_in(&tmp_root).with_context(|_| {
-                ErrorDetails::CreateTempFileError {
-                    in_dir: get_tmp_root(),
-                }
-            })?;
-
-            // Block to borrow cached for cached_file.
-            {
-                let mut cached_file: &File = cached.as_file();
-                cached_file
-                    .write(response_text.as_bytes())
-                    .with_context(|_| ErrorDetails::WriteNodeIndexCacheError {
-                        file: cached.path().to_path_buf(),
-                    })?;
-            }
+    {
+        let mut file: &File = tmp.as_file();
+        file.write_all(contents)
+            .with_context(|_| make_error(tmp.path().to_path_buf()))?;
+    }
 
-            let index_cache_file = path::node_index_file()?;
-            ensure_containing_dir_exists(&index_cache_file)?;
-            cached.persist(&index_cache_file).with_context(|_| {
-                ErrorDetails::WriteNodeIndexCacheError {
-                    file: index_cache_file,
-                }
-            })?;
-
-            let expiry = NamedTempFile::new_in(&tmp_root).with_context(|_| {
-                ErrorDetails::CreateTempFileError {
-                    in_dir: get_tmp_root(),
-                }
-            })?;
-
-            // Block to borrow expiry for expiry_file.
-            {
-                let mut expiry_file: &File = expiry.as_file();
-
-                let result = if let Some(expires_header) = response.headers().get_011::<Expires>() {
-                    write!(expiry_file, "{}", expires_header)
-                } else {
-                    let expiry_date =
-                        SystemTime::now() + Duration::from_secs(max_age(&response).into());
-
-                    write!(expiry_file, "{}", HttpDate::from(expiry_date))
-                };
-
-                result.with_context(|_| ErrorDetails::WriteNodeIndexExpiryError {
-                    file: expiry.path().to_path_buf(),
-                })?;
-            }
+    ensure_containing_dir_exists(&destination)?;
+    tmp.persist(&destination)
+        .with_context(|_| make_error(destination.clone()))?;
+    Ok(())
+}
+
+/// Writes the fetched index body to the on-disk cache, prefixing it with a
+/// self-describing header that records the source `url` and a fingerprint of the
+/// body. A later read can then reject a cache fetched from a different source, or
+/// one left half-written, without relying on the loosely-coupled expiry file.
+fn write_cache_index(url: &str, response_text: &str) -> Fallible<()> {
+    let index_file = path::node_index_file()?;
+    let header = CacheHeader {
+        url: url.to_string(),
+        fingerprint: fingerprint(response_text),
+    };
+    let header_json =
+        serde_json::to_string(&header).with_context(|_| ErrorDetails::WriteNodeIndexCacheError {
+            file: index_file.clone(),
+        })?;
+    let contents = format!("{}{}\n{}", CACHE_HEADER_PREFIX, header_json, response_text);
+
+    persist_cache_file(contents.as_bytes(), index_file, |file| {
+        ErrorDetails::WriteNodeIndexCacheError { file }
+    })
+}
+
+/// Writes the cache expiry, preferring the response `Expires` header and
+/// otherwise deriving it from `Cache-Control: max-age`.
+fn write_cache_expiry(response: &reqwest::Response) -> Fallible<()> {
+    let expiry = if let Some(expires_header) = response.headers().get_011::<Expires>() {
+        expires_header.to_string()
+    } else {
+        let expiry_date = SystemTime::now() + Duration::from_secs(max_age(response).into());
+        HttpDate::from(expiry_date).to_string()
+    };
+
+    persist_cache_file(expiry.as_bytes(), path::node_index_expiry_file()?, |file| {
+        ErrorDetails::WriteNodeIndexExpiryError { file }
+    })
+}
+
+/// Records the response `ETag`/`Last-Modified` validators for a future
+/// conditional request. A response carrying neither leaves any existing file in
+/// place rather than writing an empty one.
+fn write_cache_validators(response: &reqwest::Response) -> Fallible<()> {
+    let validators = CacheValidators::from_response(response);
+    if validators.is_empty() {
+        return Ok(());
+    }
+
+    let etag_file = path::node_index_etag_file()?;
+    let serialized = serde_json::to_string(&validators)
+        .with_context(|_| ErrorDetails::WriteNodeIndexCacheError {
+            file: etag_file.clone(),
+        })?;
+
+    persist_cache_file(serialized.as_bytes(), etag_file, |file| {
+        ErrorDetails::WriteNodeIndexCacheError { file }
+    })
+}
+
+/// Fetches the index live (revalidating conditionally) and refreshes the cache.
+fn fetch_node_index(url: &str) -> Fallible<serial::RawNodeIndex> {
+    // Revalidate any stale cached copy conditionally, so the registry can
+    // answer 304 Not Modified and spare us the download.
+    let validators = read_cached_validators()?;
+    let mut builder = reqwest::Client::new().get(url);
+    if let Some(ref validators) = validators {
+        if let Some(ref etag) = validators.etag {
+            builder = builder.header(IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(ref last_modified) = validators.last_modified {
+            builder = builder.header(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+    }
+
+    // Only a failure to reach the registry (DNS failure, timeout, offline) is
+    // eligible for the stale-cache fallback. Once we have a response in hand, any
+    // later error (a corrupt body, an unwritable cache) is a real failure that
+    // must surface rather than be masked by serving a stale copy.
+    let mut response: reqwest::Response = match builder.send().with_context(registry_fetch_error("Node", url)) {
+        Ok(response) => response,
+        Err(error) => return stale_cache_fallback(url, error),
+    };
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        if let Some(cached_text) = read_cached_index_body(url)? {
+            debug!("Node index unchanged (304 Not Modified); reusing cached copy");
+            let index = serde_json::de::from_str(&cached_text)
+                .with_context(|_| ErrorDetails::ParseNodeIndexCacheError)?;
+            // The registry confirmed the copy is current, so only the expiry
+            // needs refreshing from this response.
+            write_cache_expiry(&response)?;
+            return Ok(index);
+        }
 
-            let index_expiry_file = path::node_index_expiry_file()?;
-            ensure_containing_dir_exists(&index_expiry_file)?;
-            expiry.persist(&index_expiry_file).with_context(|_| {
-                ErrorDetails::WriteNodeIndexExpiryError {
-                    file: index_expiry_file,
-                }
-            })?;
+        // The validators outlived a usable index file (it is missing or failed
+        // its header check); fetch unconditionally.
+        debug!("Received 304 but cached index is missing or unusable; refetching");
+        response = reqwest::get(url).with_context(registry_fetch_error("Node", url))?;
+    }
+
+    let response_text = response
+        .text()
+        .with_context(registry_fetch_error("Node", url))?;
+    let index: serial::RawNodeIndex = serde_json::de::from_str(&response_text)
+        .with_context(|_| ErrorDetails::ParseNodeIndexError {
+            from_url: url.to_string(),
+        })?;
+
+    write_cache_index(url, &response_text)?;
+    write_cache_expiry(&response)?;
+    write_cache_validators(&response)?;
+
+    Ok(index)
+}
+
+/// Serves a merely-expired cached index after the registry could not be reached,
+/// so resolution keeps working offline against already-seen versions. When no
+/// usable cache is on disk the original connection `error` is returned unchanged.
+fn stale_cache_fallback(url: &str, error: VoltaError) -> Fallible<serial::RawNodeIndex> {
+    match read_cached_index_body(url)? {
+        Some(cached_text) => {
+            debug!(
+                "Could not reach the Node registry ({}); falling back to stale cached copy",
+                error
+            );
+            serde_json::de::from_str(&cached_text)
+                .with_context(|_| ErrorDetails::ParseNodeIndexCacheError)
+        }
+        None => Err(error),
+    }
+}
+
+fn resolve_node_versions(url: &str) -> Fallible<serial::RawNodeIndex> {
+    // A one-shot force-refresh bypasses the expiry check for this resolution only.
+    let cached = if FORCE_INDEX_REFRESH.swap(false, Ordering::SeqCst) {
+        None
+    } else {
+        read_cached_opt(url)?
+    };
 
+    match cached {
+        Some(serial) => Ok(serial),
+        None => {
+            let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
+            let result = fetch_node_index(url);
             spinner.finish_and_clear();
-            Ok(index)
+            result
         }
     }
 }
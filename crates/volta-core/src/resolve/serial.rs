@@ -0,0 +1,105 @@
+//! Serialized (on-the-wire) representations of the public Node index, and the
+//! conversion into the strongly-typed [`NodeIndex`] the resolver works with.
+
+use std::collections::HashSet;
+
+use super::node::{NodeDistroFiles, NodeEntry, NodeIndex};
+use crate::error::ErrorDetails;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use volta_fail::{Fallible, ResultExt};
+
+/// The raw index as delivered by the registry: a newest-to-oldest array of
+/// per-version entries.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RawNodeIndex(Vec<RawNodeEntry>);
+
+#[derive(Serialize, Deserialize, Debug)]
+struct RawNodeEntry {
+    version: String,
+    // Pre-release lines and the very oldest entries predate bundled npm and omit
+    // this field; such entries are not installable and are dropped in `into_index`.
+    #[serde(default)]
+    npm: Option<String>,
+    files: Vec<String>,
+    lts: RawNodeLts,
+}
+
+/// The wire shape of an entry's `lts` field: `false` for a non-LTS release, or
+/// the release-line codename string (e.g. `"Gallium"`) for an LTS release.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+enum RawNodeLts {
+    Line(String),
+    NotLts(bool),
+}
+
+impl RawNodeLts {
+    /// Collapses the wire value into the codename carried by [`NodeEntry::lts`].
+    fn into_codename(self) -> Option<String> {
+        match self {
+            RawNodeLts::Line(codename) => Some(codename),
+            RawNodeLts::NotLts(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_non_lts_as_none() {
+        let lts: RawNodeLts = serde_json::from_str("false").expect("valid lts value");
+        assert_eq!(lts.into_codename(), None);
+    }
+
+    #[test]
+    fn deserializes_codename_as_some() {
+        let lts: RawNodeLts = serde_json::from_str("\"Gallium\"").expect("valid lts value");
+        assert_eq!(lts.into_codename(), Some("Gallium".to_string()));
+    }
+
+    #[test]
+    fn into_index_carries_the_lts_codename() {
+        let raw: RawNodeIndex = serde_json::from_str(
+            r#"[
+                {"version":"v16.14.0","npm":"8.3.1","files":[],"lts":"Gallium"},
+                {"version":"v17.5.0","npm":"8.4.1","files":[],"lts":false}
+            ]"#,
+        )
+        .expect("valid index");
+        let index = raw.into_index().expect("index converts");
+        assert_eq!(index.entries[0].lts, Some("Gallium".to_string()));
+        assert_eq!(index.entries[1].lts, None);
+    }
+}
+
+impl RawNodeIndex {
+    pub fn into_index(self) -> Fallible<NodeIndex> {
+        let mut entries = Vec::new();
+        for entry in self.0 {
+            if let Some(npm) = entry.npm {
+                let data = NodeDistroFiles {
+                    files: entry.files.into_iter().collect::<HashSet<String>>(),
+                };
+                // Node version strings are prefixed with a `v` (e.g. `v14.2.0`).
+                let version = Version::parse(&entry.version[1..]).with_context(|_| {
+                    ErrorDetails::ParseVersionError {
+                        value: entry.version.clone(),
+                    }
+                })?;
+                let npm = Version::parse(&npm).with_context(|_| {
+                    ErrorDetails::ParseVersionError { value: npm.clone() }
+                })?;
+                entries.push(NodeEntry {
+                    version,
+                    npm,
+                    files: data,
+                    lts: entry.lts.into_codename(),
+                });
+            }
+        }
+        Ok(NodeIndex { entries })
+    }
+}
@@ -0,0 +1,159 @@
+//! Parsing and display of the version specifiers users pass to Volta, e.g.
+//! `node@14`, `node@lts`, `node@lts/gallium`, or `node@latest`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ErrorDetails;
+use semver::{Version, VersionReq};
+use volta_fail::{Fallible, ResultExt};
+
+/// A request for a tool version, as typed on the command line.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VersionSpec {
+    /// The newest available version.
+    Latest,
+    /// The newest LTS (Long Term Support) version.
+    Lts,
+    /// The newest version on a specific LTS line, named by its codename
+    /// (e.g. `lts/gallium`). Matched case-insensitively against the index.
+    LtsLine(String),
+    /// The newest version satisfying a semver requirement.
+    Semver(VersionReq),
+    /// A single exact version.
+    Exact(Version),
+}
+
+impl Default for VersionSpec {
+    fn default() -> Self {
+        VersionSpec::Latest
+    }
+}
+
+impl fmt::Display for VersionSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VersionSpec::Latest => write!(f, "latest"),
+            VersionSpec::Lts => write!(f, "lts"),
+            VersionSpec::LtsLine(codename) => write!(f, "lts/{}", codename),
+            VersionSpec::Semver(req) => write!(f, "{}", req),
+            VersionSpec::Exact(version) => write!(f, "{}", version),
+        }
+    }
+}
+
+impl VersionSpec {
+    pub fn exact(version: &Version) -> Self {
+        VersionSpec::Exact(version.clone())
+    }
+
+    pub fn parse(value: impl AsRef<str>) -> Fallible<Self> {
+        let value = value.as_ref().trim();
+        value.parse()
+    }
+
+    pub fn parse_version(version: impl AsRef<str>) -> Fallible<Version> {
+        let version = version.as_ref().trim();
+        Version::parse(version).with_context(|_| ErrorDetails::ParseVersionError {
+            value: version.to_string(),
+        })
+    }
+
+    pub fn parse_requirements(requirements: impl AsRef<str>) -> Fallible<VersionReq> {
+        let requirements = requirements.as_ref().trim();
+        VersionReq::parse(requirements).with_context(|_| ErrorDetails::ParseVersionError {
+            value: requirements.to_string(),
+        })
+    }
+}
+
+impl FromStr for VersionSpec {
+    type Err = volta_fail::VoltaError;
+
+    fn from_str(s: &str) -> Fallible<Self> {
+        let s = s.trim();
+
+        if s.eq_ignore_ascii_case("latest") {
+            return Ok(VersionSpec::Latest);
+        }
+
+        if s.eq_ignore_ascii_case("lts") {
+            return Ok(VersionSpec::Lts);
+        }
+
+        // `lts/<codename>` pins a specific maintenance line. The codename is kept
+        // verbatim and compared case-insensitively when resolving against the index.
+        if let Some(codename) = strip_prefix_ignore_ascii_case(s, "lts/") {
+            if codename.is_empty() {
+                return Ok(VersionSpec::Lts);
+            }
+            return Ok(VersionSpec::LtsLine(codename.to_string()));
+        }
+
+        // A bare, fully-specified version is treated as an exact pin; anything
+        // looser (`14`, `^14`, `>=12 <15`, ...) is a semver requirement.
+        match Version::parse(s) {
+            Ok(version) => Ok(VersionSpec::Exact(version)),
+            Err(_) => VersionSpec::parse_requirements(s).map(VersionSpec::Semver),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lts_line() {
+        assert_eq!(
+            VersionSpec::parse("lts/gallium").unwrap(),
+            VersionSpec::LtsLine("gallium".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_lts_line_case_insensitively() {
+        // The `lts/` marker is matched case-insensitively; the codename itself is
+        // preserved verbatim for the resolver to compare.
+        assert_eq!(
+            VersionSpec::parse("LTS/Gallium").unwrap(),
+            VersionSpec::LtsLine("Gallium".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_bare_and_empty_lts_as_newest_lts() {
+        assert_eq!(VersionSpec::parse("lts").unwrap(), VersionSpec::Lts);
+        assert_eq!(VersionSpec::parse("LTS/").unwrap(), VersionSpec::Lts);
+    }
+
+    #[test]
+    fn parses_semver_requirement() {
+        assert_eq!(
+            VersionSpec::parse("^14").unwrap(),
+            VersionSpec::Semver(semver::VersionReq::parse("^14").unwrap())
+        );
+    }
+
+    #[test]
+    fn rejects_multibyte_input_without_panicking() {
+        // A multibyte char whose bytes straddle the `lts/` boundary must surface a
+        // parse error rather than panic on a non-char-boundary slice.
+        assert!(VersionSpec::parse("lts\u{20ac}").is_err());
+    }
+}
+
+/// Strips `prefix` from the front of `value`, ignoring ASCII case, returning the
+/// remainder when it matched. Compares raw bytes so a multibyte character
+/// straddling the prefix boundary yields `None` rather than panicking.
+fn strip_prefix_ignore_ascii_case<'a>(value: &'a str, prefix: &str) -> Option<&'a str> {
+    let matches = value
+        .as_bytes()
+        .get(..prefix.len())
+        .map_or(false, |head| head.eq_ignore_ascii_case(prefix.as_bytes()));
+    if matches {
+        Some(&value[prefix.len()..])
+    } else {
+        None
+    }
+}